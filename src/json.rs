@@ -1,4 +1,4 @@
-use crate::{validation::NotValidated, JsonExtractInternalFut};
+use crate::{json_config::UNSET_LIMIT, validation::NotValidated, JsonExtractInternalFut};
 use actix_web::{
     body::EitherBody,
     dev::{self},
@@ -14,16 +14,21 @@ use std::{
     task::{Context, Poll},
 };
 
-pub struct Json<T>(pub T);
+/// JSON extractor/responder with a compile-time payload size limit.
+///
+/// Left unspecified, `LIMIT` defers to `JsonConfig::limit` from app data (itself 2MB by
+/// default). Set an explicit `LIMIT` (e.g. `Json<TodoData, 4096>`) to pin the limit for
+/// this handler regardless of `JsonConfig`, including a `LIMIT` that happens to equal 2MB.
+pub struct Json<T, const LIMIT: usize = UNSET_LIMIT>(pub T);
 
-impl<T> Json<T> {
+impl<T, const LIMIT: usize> Json<T, LIMIT> {
     /// Unwrap into inner `T` value.
     pub fn into_inner(self) -> T {
         self.0
     }
 }
 
-impl<T> Deref for Json<T> {
+impl<T, const LIMIT: usize> Deref for Json<T, LIMIT> {
     type Target = T;
 
     fn deref(&self) -> &T {
@@ -31,25 +36,25 @@ impl<T> Deref for Json<T> {
     }
 }
 
-impl<T> DerefMut for Json<T> {
+impl<T, const LIMIT: usize> DerefMut for Json<T, LIMIT> {
     fn deref_mut(&mut self) -> &mut T {
         &mut self.0
     }
 }
 
-impl<T: Debug> Debug for Json<T> {
+impl<T: Debug, const LIMIT: usize> Debug for Json<T, LIMIT> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Debug::fmt(&self.0, f)
     }
 }
 
-impl<T: Display> Display for Json<T> {
+impl<T: Display, const LIMIT: usize> Display for Json<T, LIMIT> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Display::fmt(&self.0, f)
     }
 }
 
-impl<T: Serialize> Serialize for Json<T> {
+impl<T: Serialize, const LIMIT: usize> Serialize for Json<T, LIMIT> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -61,7 +66,7 @@ impl<T: Serialize> Serialize for Json<T> {
 /// Creates response with OK status code, correct content type header, and serialized JSON payload.
 ///
 /// If serialization failed
-impl<T: Serialize> Responder for Json<T> {
+impl<T: Serialize, const LIMIT: usize> Responder for Json<T, LIMIT> {
     type Body = EitherBody<String>;
 
     fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
@@ -82,20 +87,22 @@ impl<T: Serialize> Responder for Json<T> {
 }
 
 /// See [here](#extractor) for example of usage as an extractor.
-impl<T: DeserializeOwned> FromRequest for Json<T> {
+impl<T: DeserializeOwned, const LIMIT: usize> FromRequest for Json<T, LIMIT> {
     type Error = Error;
-    type Future = JsonExtractFut<T>;
+    type Future = JsonExtractFut<T, LIMIT>;
 
     #[inline]
     fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
-        JsonExtractFut(JsonExtractInternalFut::from_req_and_payload(req, payload))
+        JsonExtractFut(JsonExtractInternalFut::from_req_and_payload::<LIMIT>(
+            req, payload,
+        ))
     }
 }
 
-pub struct JsonExtractFut<T>(JsonExtractInternalFut<T, NotValidated>);
+pub struct JsonExtractFut<T, const LIMIT: usize>(JsonExtractInternalFut<T, NotValidated>);
 
-impl<T: for<'de> DeserializeOwned> Future for JsonExtractFut<T> {
-    type Output = Result<Json<T>>;
+impl<T: for<'de> DeserializeOwned, const LIMIT: usize> Future for JsonExtractFut<T, LIMIT> {
+    type Output = Result<Json<T, LIMIT>>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         match Future::poll(unsafe { self.map_unchecked_mut(|v| &mut v.0) }, cx) {
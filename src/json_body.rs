@@ -0,0 +1,83 @@
+use crate::json_config::{trace_error, trace_ok};
+use actix_web::{
+    error::JsonPayloadError,
+    http::header::CONTENT_LENGTH,
+    web::{Bytes, BytesMut},
+    HttpMessage,
+};
+use awc::{error::PayloadError, ClientResponse};
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+
+/// Extension trait adding tracing-instrumented JSON body extraction to [`awc::ClientResponse`].
+///
+/// Mirrors the server-side [`crate::Json`] extractor: the raw body is dumped (up to 30KB)
+/// to the `tracing` `error!`/`trace!` targets, giving outgoing request responses the same
+/// observability as incoming payloads.
+pub trait JsonBody {
+    /// Buffer the response body (up to `limit` bytes) and deserialize it as JSON.
+    fn json_traced<T>(
+        &mut self,
+        limit: usize,
+    ) -> impl Future<Output = Result<T, JsonPayloadError>> + '_
+    where
+        T: DeserializeOwned + 'static;
+}
+
+impl<S> JsonBody for ClientResponse<S>
+where
+    S: Stream<Item = Result<Bytes, PayloadError>> + Unpin,
+{
+    async fn json_traced<T>(&mut self, limit: usize) -> Result<T, JsonPayloadError>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        // check content-type, the same way `JsonExtractInternalFut::new` does.
+        let can_parse_json = match self.mime_type() {
+            Ok(Some(mime)) => mime.subtype() == mime::JSON || mime.suffix() == Some(mime::JSON),
+            _ => false,
+        };
+
+        if !can_parse_json {
+            return Err(JsonPayloadError::ContentType);
+        }
+
+        let length = self
+            .headers()
+            .get(&CONTENT_LENGTH)
+            .and_then(|l| l.to_str().ok())
+            .and_then(|s| s.parse::<usize>().ok());
+
+        if let Some(length) = length {
+            if length > limit {
+                return Err(JsonPayloadError::OverflowKnownLength { length, limit });
+            }
+        }
+
+        let mut buf = BytesMut::with_capacity(8192);
+
+        while let Some(chunk) = self.next().await {
+            let chunk = chunk.map_err(JsonPayloadError::Payload)?;
+            let buf_len = buf.len() + chunk.len();
+
+            if buf_len > limit {
+                trace_error(&buf);
+                return Err(JsonPayloadError::Overflow { limit });
+            }
+
+            buf.extend_from_slice(&chunk);
+        }
+
+        match serde_json::from_slice::<T>(&buf) {
+            Ok(v) => {
+                trace_ok(&buf);
+                Ok(v)
+            }
+            Err(e) => {
+                trace_error(&buf);
+                Err(JsonPayloadError::Deserialize(e))
+            }
+        }
+    }
+}
@@ -19,10 +19,14 @@ use std::{
 };
 use tracing::{error, trace};
 
+/// Closure invoked in place of the default `JsonPayloadError -> Error` conversion.
+pub(super) type ErrorHandler = Arc<dyn Fn(JsonPayloadError, &HttpRequest) -> Error + Send + Sync>;
+
 #[derive(Clone)]
 pub struct JsonConfig {
     pub(super) content_type: Option<Arc<dyn Fn(Mime) -> bool + Send + Sync>>,
     pub(super) content_type_required: bool,
+    pub(super) err_handler: Option<ErrorHandler>,
     pub(super) limit: usize,
 }
 
@@ -48,6 +52,17 @@ impl JsonConfig {
         self
     }
 
+    /// Set a custom error handler, invoked with the error that occurred while parsing
+    /// the request, and the request itself. Use it to build a custom response, e.g. to
+    /// return a problem+json body or downgrade a content-type mismatch to 415.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(JsonPayloadError, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
     /// Extract payload config from app data. Check both `T` and `Data<T>`, in that order, and fall
     /// back to the default payload config.
     pub(crate) fn from_req(req: &HttpRequest) -> &Self {
@@ -57,13 +72,19 @@ impl JsonConfig {
     }
 }
 
-const DEFAULT_LIMIT: usize = 2_097_152; // 2 mb
+pub(crate) const DEFAULT_LIMIT: usize = 2_097_152; // 2 mb
+
+/// Sentinel `LIMIT` for `Json<T>`/`JsonValid<T>` meaning "no const limit was given", so
+/// `JsonConfig::limit` from app data is used instead. Not a valid payload size, so an
+/// explicit `LIMIT` (even one equal to `DEFAULT_LIMIT`) can always be told apart from it.
+pub(crate) const UNSET_LIMIT: usize = usize::MAX;
 
 /// Allow shared refs used as default.
 const DEFAULT_CONFIG: JsonConfig = JsonConfig {
     limit: DEFAULT_LIMIT,
     content_type: None,
     content_type_required: true,
+    err_handler: None,
 };
 
 impl Default for JsonConfig {
@@ -73,7 +94,11 @@ impl Default for JsonConfig {
 }
 
 pub(super) enum JsonExtractInternalFut<T, V> {
-    Error(Option<JsonPayloadError>),
+    Error {
+        err: Option<JsonPayloadError>,
+        err_handler: Option<ErrorHandler>,
+        req: HttpRequest,
+    },
     Body {
         limit: usize,
         /// Length as reported by `Content-Length` header, if present.
@@ -83,6 +108,8 @@ pub(super) enum JsonExtractInternalFut<T, V> {
         #[cfg(not(feature = "__compress"))]
         payload: Payload,
         buf: BytesMut,
+        err_handler: Option<ErrorHandler>,
+        req: HttpRequest,
         _res: PhantomData<T>,
         _v: PhantomData<V>,
     },
@@ -91,14 +118,29 @@ pub(super) enum JsonExtractInternalFut<T, V> {
 impl<T, V> Unpin for JsonExtractInternalFut<T, V> {}
 
 impl<T: DeserializeOwned, V: Valid<T>> JsonExtractInternalFut<T, V> {
-    pub fn from_req_and_payload(req: &HttpRequest, payload: &mut dev::Payload) -> Self {
+    /// Build the future for a handler-local `LIMIT`. An explicit `LIMIT` takes precedence
+    /// over `JsonConfig::limit` resolved from app data; `UNSET_LIMIT` falls back to it.
+    pub fn from_req_and_payload<const LIMIT: usize>(
+        req: &HttpRequest,
+        payload: &mut dev::Payload,
+    ) -> Self {
         let config = JsonConfig::from_req(req);
 
-        let limit = config.limit;
         let ctype_required = config.content_type_required;
         let ctype_fn = config.content_type.as_deref();
+        let err_handler = config.err_handler.clone();
+
+        // `LIMIT` is left at `UNSET_LIMIT` when the caller writes `Json<T>`/`JsonValid<T>`
+        // without a const argument, in which case `JsonConfig::limit` from app data (if any)
+        // still applies. Any other `LIMIT`, including one equal to `DEFAULT_LIMIT`, is an
+        // explicit pin and always takes precedence.
+        let limit = if LIMIT == UNSET_LIMIT {
+            config.limit
+        } else {
+            LIMIT
+        };
 
-        Self::new(req, payload, ctype_fn, ctype_required).limit(limit)
+        Self::new(req, payload, ctype_fn, ctype_required, err_handler).limit(limit)
     }
 
     /// Create a new future to decode a JSON request payload.
@@ -108,6 +150,7 @@ impl<T: DeserializeOwned, V: Valid<T>> JsonExtractInternalFut<T, V> {
         payload: &mut Payload,
         ctype_fn: Option<&(dyn Fn(mime::Mime) -> bool + Send + Sync)>,
         ctype_required: bool,
+        err_handler: Option<ErrorHandler>,
     ) -> Self {
         // check content-type
         let can_parse_json = if let Ok(Some(mime)) = req.mime_type() {
@@ -121,7 +164,11 @@ impl<T: DeserializeOwned, V: Valid<T>> JsonExtractInternalFut<T, V> {
         };
 
         if !can_parse_json {
-            return Self::Error(Some(JsonPayloadError::ContentType));
+            return Self::Error {
+                err: Some(JsonPayloadError::ContentType),
+                err_handler,
+                req: req.clone(),
+            };
         }
 
         let length = req
@@ -148,9 +195,11 @@ impl<T: DeserializeOwned, V: Valid<T>> JsonExtractInternalFut<T, V> {
             _res: PhantomData,
             _v: PhantomData,
             buf: BytesMut::with_capacity(8192),
+            err_handler,
             length,
             limit: DEFAULT_LIMIT,
             payload,
+            req: req.clone(),
         }
     }
 
@@ -159,16 +208,19 @@ impl<T: DeserializeOwned, V: Valid<T>> JsonExtractInternalFut<T, V> {
         match self {
             Self::Body {
                 buf,
+                err_handler,
                 length,
                 payload,
+                req,
                 ..
             } => {
                 if let Some(len) = length {
                     if len > limit {
-                        return Self::Error(Some(JsonPayloadError::OverflowKnownLength {
-                            length: len,
-                            limit,
-                        }));
+                        return Self::Error {
+                            err: Some(JsonPayloadError::OverflowKnownLength { length: len, limit }),
+                            err_handler,
+                            req,
+                        };
                     }
                 }
 
@@ -176,46 +228,83 @@ impl<T: DeserializeOwned, V: Valid<T>> JsonExtractInternalFut<T, V> {
                     _res: PhantomData,
                     _v: PhantomData,
                     buf,
+                    err_handler,
                     length,
                     limit,
                     payload,
+                    req,
                 }
             }
-            Self::Error(e) => Self::Error(e),
+            Self::Error {
+                err,
+                err_handler,
+                req,
+            } => Self::Error {
+                err,
+                err_handler,
+                req,
+            },
         }
     }
 
-    fn poll_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<BytesMut, Error>> {
+    /// Poll the buffered body, alongside the error handler and request needed to convert
+    /// any `JsonPayloadError` that comes up along the way.
+    #[allow(clippy::type_complexity)]
+    fn poll_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<(
+        Result<BytesMut, JsonPayloadError>,
+        Option<ErrorHandler>,
+        HttpRequest,
+    )> {
         let this = self.get_mut();
 
         match this {
             Self::Body {
                 buf,
+                err_handler,
                 limit,
                 payload,
+                req,
                 ..
             } => loop {
                 let res = ready!(Pin::new(&mut *payload).poll_next(cx));
 
                 match res {
                     Some(chunk) => {
-                        let chunk = chunk?;
+                        let chunk = match chunk {
+                            Ok(chunk) => chunk,
+                            Err(e) => {
+                                return Poll::Ready((
+                                    Err(JsonPayloadError::Payload(e)),
+                                    err_handler.clone(),
+                                    req.clone(),
+                                ))
+                            }
+                        };
                         let buf_len = buf.len() + chunk.len();
 
                         if buf_len > *limit {
                             trace_error(buf);
 
-                            return Poll::Ready(Err(
-                                JsonPayloadError::Overflow { limit: *limit }.into()
+                            return Poll::Ready((
+                                Err(JsonPayloadError::Overflow { limit: *limit }),
+                                err_handler.clone(),
+                                req.clone(),
                             ));
                         } else {
                             buf.extend_from_slice(&chunk);
                         }
                     }
-                    None => return Poll::Ready(Ok(take(buf))),
+                    None => return Poll::Ready((Ok(take(buf)), err_handler.clone(), req.clone())),
                 }
             },
-            Self::Error(e) => Poll::Ready(Err(e.take().unwrap().into())),
+            Self::Error {
+                err,
+                err_handler,
+                req,
+            } => Poll::Ready((Err(err.take().unwrap()), err_handler.clone(), req.clone())),
         }
     }
 }
@@ -224,24 +313,45 @@ impl<T: DeserializeOwned, V: Valid<T>> Future for JsonExtractInternalFut<T, V> {
     type Output = Result<T, Error>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        match self.poll_bytes(cx) {
-            Poll::Pending => Poll::Pending,
-            Poll::Ready(Ok(bytes)) => match serde_json::from_slice::<T>(&bytes) {
-                Ok(v) => {
-                    trace_ok(&bytes);
-                    V::valid(&v)?;
-                    Poll::Ready(Ok(v))
-                }
-                Err(e) => {
-                    trace_error(&bytes);
-                    Poll::Ready(Err(JsonPayloadError::Deserialize(e).into()))
-                }
-            },
-            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+        let (res, err_handler, req) = match self.poll_bytes(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(parts) => parts,
+        };
+
+        let bytes = match res {
+            Ok(bytes) => bytes,
+            Err(e) => return Poll::Ready(Err(dispatch_err(e, err_handler.as_ref(), &req))),
+        };
+
+        match serde_json::from_slice::<T>(&bytes) {
+            Ok(v) => {
+                trace_ok(&bytes);
+                V::valid(&v)?;
+                Poll::Ready(Ok(v))
+            }
+            Err(e) => {
+                trace_error(&bytes);
+                Poll::Ready(Err(dispatch_err(
+                    JsonPayloadError::Deserialize(e),
+                    err_handler.as_ref(),
+                    &req,
+                )))
+            }
         }
     }
 }
 
+fn dispatch_err(
+    err: JsonPayloadError,
+    err_handler: Option<&ErrorHandler>,
+    req: &HttpRequest,
+) -> Error {
+    match err_handler {
+        Some(f) => f(err, req),
+        None => err.into(),
+    }
+}
+
 fn text_repr(mut bytes: &[u8]) -> Cow<str> {
     const KB: usize = 1024;
     const _30KB: usize = 30 * KB;
@@ -253,10 +363,132 @@ fn text_repr(mut bytes: &[u8]) -> Cow<str> {
     String::from_utf8_lossy(bytes)
 }
 
-fn trace_error(bytes: &[u8]) {
+pub(crate) fn trace_error(bytes: &[u8]) {
     error!(text = %text_repr(bytes), "json");
 }
 
-fn trace_ok(bytes: &[u8]) {
+pub(crate) fn trace_ok(bytes: &[u8]) {
     trace!(text = %text_repr(bytes), "json");
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Json, JsonConfig};
+    use actix_web::{
+        error::ErrorInternalServerError, http::header::CONTENT_TYPE, http::StatusCode,
+        test::TestRequest, FromRequest,
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Greeting {
+        #[allow(dead_code)]
+        message: String,
+    }
+
+    #[actix_web::test]
+    async fn json_without_const_limit_respects_json_config() {
+        let config = JsonConfig::default().limit(5);
+        let (req, mut payload) = TestRequest::post()
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .set_payload(r#"{"message":"hi"}"#)
+            .app_data(config)
+            .to_http_parts();
+
+        let err = Json::<Greeting>::from_request(&req, &mut payload)
+            .await
+            .expect_err("body exceeds the configured limit");
+
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[actix_web::test]
+    async fn json_with_const_limit_overrides_json_config() {
+        let config = JsonConfig::default().limit(10_000_000);
+        let (req, mut payload) = TestRequest::post()
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .set_payload(r#"{"message":"hi"}"#)
+            .app_data(config)
+            .to_http_parts();
+
+        let err = Json::<Greeting, 5>::from_request(&req, &mut payload)
+            .await
+            .expect_err("const LIMIT should override a larger JsonConfig::limit");
+
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[actix_web::test]
+    async fn error_handler_fires_on_content_type_mismatch() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        let config = JsonConfig::default().error_handler(move |_, _| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+            ErrorInternalServerError("traced")
+        });
+
+        let (req, mut payload) = TestRequest::post()
+            .set_payload(r#"{"message":"hi"}"#)
+            .app_data(config)
+            .to_http_parts();
+
+        Json::<Greeting>::from_request(&req, &mut payload)
+            .await
+            .expect_err("missing content-type should fail");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[actix_web::test]
+    async fn error_handler_fires_on_overflow() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        let config = JsonConfig::default().limit(5).error_handler(move |_, _| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+            ErrorInternalServerError("traced")
+        });
+
+        let (req, mut payload) = TestRequest::post()
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .set_payload(r#"{"message":"hi"}"#)
+            .app_data(config)
+            .to_http_parts();
+
+        Json::<Greeting>::from_request(&req, &mut payload)
+            .await
+            .expect_err("body exceeds the configured limit");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[actix_web::test]
+    async fn error_handler_fires_on_deserialize_failure() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        let config = JsonConfig::default().error_handler(move |_, _| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+            ErrorInternalServerError("traced")
+        });
+
+        let (req, mut payload) = TestRequest::post()
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .set_payload("not json")
+            .app_data(config)
+            .to_http_parts();
+
+        Json::<Greeting>::from_request(&req, &mut payload)
+            .await
+            .expect_err("malformed body should fail to deserialize");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,6 +1,12 @@
-use crate::{json_config::JsonExtractInternalFut, Validated};
-use actix_web::{dev, Error, FromRequest, HttpRequest, Result};
-use serde::de::DeserializeOwned;
+use crate::{
+    json_config::{JsonExtractInternalFut, UNSET_LIMIT},
+    Valid, Validated,
+};
+use actix_web::{
+    body::EitherBody, dev, error::JsonPayloadError, Error, FromRequest, HttpRequest, HttpResponse,
+    Responder, Result,
+};
+use serde::{de::DeserializeOwned, Serialize};
 use std::{
     fmt::{self, Debug, Display},
     future::Future,
@@ -10,16 +16,21 @@ use std::{
 };
 use validator::Validate;
 
-pub struct JsonValid<T>(pub T);
+/// JSON extractor/responder with validation and a compile-time payload size limit.
+///
+/// Left unspecified, `LIMIT` defers to `JsonConfig::limit` from app data (itself 2MB by
+/// default). Set an explicit `LIMIT` (e.g. `JsonValid<TodoData, 4096>`) to pin the limit
+/// for this handler regardless of `JsonConfig`, including a `LIMIT` that happens to equal 2MB.
+pub struct JsonValid<T, const LIMIT: usize = UNSET_LIMIT>(pub T);
 
-impl<T> JsonValid<T> {
+impl<T, const LIMIT: usize> JsonValid<T, LIMIT> {
     /// Unwrap into inner `T` value.
     pub fn into_inner(self) -> T {
         self.0
     }
 }
 
-impl<T> Deref for JsonValid<T> {
+impl<T, const LIMIT: usize> Deref for JsonValid<T, LIMIT> {
     type Target = T;
 
     fn deref(&self) -> &T {
@@ -27,39 +38,72 @@ impl<T> Deref for JsonValid<T> {
     }
 }
 
-impl<T> DerefMut for JsonValid<T> {
+impl<T, const LIMIT: usize> DerefMut for JsonValid<T, LIMIT> {
     fn deref_mut(&mut self) -> &mut T {
         &mut self.0
     }
 }
 
-impl<T: Debug> Debug for JsonValid<T> {
+impl<T: Debug, const LIMIT: usize> Debug for JsonValid<T, LIMIT> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Debug::fmt(&self.0, f)
     }
 }
 
-impl<T: Display> Display for JsonValid<T> {
+impl<T: Display, const LIMIT: usize> Display for JsonValid<T, LIMIT> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Display::fmt(&self.0, f)
     }
 }
 
+/// Creates response with OK status code, correct content type header, and serialized JSON
+/// payload, after validating `T` against its own schema.
+///
+/// If the value fails validation, an internal server error is returned rather than
+/// serializing and emitting a payload that violates its own contract.
+impl<T: Serialize + Validate, const LIMIT: usize> Responder for JsonValid<T, LIMIT> {
+    type Body = EitherBody<String>;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        if Validated::valid(&self.0).is_err() {
+            return HttpResponse::InternalServerError()
+                .finish()
+                .map_into_right_body();
+        }
+
+        match serde_json::to_string(&self.0) {
+            Ok(body) => match HttpResponse::Ok()
+                .content_type(mime::APPLICATION_JSON)
+                .message_body(body)
+            {
+                Ok(res) => res.map_into_left_body(),
+                Err(err) => HttpResponse::from_error(err).map_into_right_body(),
+            },
+
+            Err(err) => {
+                HttpResponse::from_error(JsonPayloadError::Serialize(err)).map_into_right_body()
+            }
+        }
+    }
+}
+
 /// See [here](#extractor) for example of usage as an extractor.
-impl<T: DeserializeOwned + Validate> FromRequest for JsonValid<T> {
+impl<T: DeserializeOwned + Validate, const LIMIT: usize> FromRequest for JsonValid<T, LIMIT> {
     type Error = Error;
-    type Future = JsonValidExtractFut<T>;
+    type Future = JsonValidExtractFut<T, LIMIT>;
 
     #[inline]
     fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
-        JsonValidExtractFut(JsonExtractInternalFut::from_req_and_payload(req, payload))
+        JsonValidExtractFut(JsonExtractInternalFut::from_req_and_payload::<LIMIT>(
+            req, payload,
+        ))
     }
 }
 
-pub struct JsonValidExtractFut<T>(JsonExtractInternalFut<T, Validated>);
+pub struct JsonValidExtractFut<T, const LIMIT: usize>(JsonExtractInternalFut<T, Validated>);
 
-impl<T: DeserializeOwned + Validate> Future for JsonValidExtractFut<T> {
-    type Output = Result<JsonValid<T>>;
+impl<T: DeserializeOwned + Validate, const LIMIT: usize> Future for JsonValidExtractFut<T, LIMIT> {
+    type Output = Result<JsonValid<T, LIMIT>>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         match Future::poll(unsafe { self.map_unchecked_mut(|v| &mut v.0) }, cx) {
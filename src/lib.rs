@@ -45,14 +45,39 @@
 //!
 //! ```
 //!
+//! # Example with a client response
+//!
+//! Same tracing behavior as [`Json`], but for bodies read from an outgoing request's
+//! response. This requires feature `client`.
+//!
+//! ```no_run
+//! use actix_web_utils::JsonBody;
+//!
+//! # async fn doc() -> Result<(), actix_web::error::JsonPayloadError> {
+//! let mut res = awc::Client::new().get("http://example.com").send().await.unwrap();
+//! let data: TodoData = res.json_traced(65_536).await?;
+//! # Ok(())
+//! # }
+//!
+//! #[derive(serde::Deserialize)]
+//! struct TodoData {
+//!     title: String,
+//! }
+//!
+//! ```
+//!
 
 mod json;
+#[cfg(feature = "client")]
+mod json_body;
 mod json_config;
 #[cfg(feature = "validator")]
 mod json_valid;
 mod validation;
 
 pub use json::Json;
+#[cfg(feature = "client")]
+pub use json_body::JsonBody;
 pub use json_config::JsonConfig;
 use json_config::JsonExtractInternalFut;
 #[cfg(feature = "validator")]